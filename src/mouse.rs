@@ -5,11 +5,24 @@ use crate::{
     COMMAND_ACKNOWLEDGED, RESEND, SELF_TEST_FAILED, SELF_TEST_PASSED,
 };
 
-pub use self::mouse_type::MouseType;
+pub use self::{
+    extra_buttons::ExtraButtons,
+    mouse_resolution::MouseResolution,
+    mouse_type::MouseType,
+    packet_builder::{MousePacketBuilder, MouseState},
+    packet_queue::PacketQueue,
+};
 
+mod extra_buttons;
+mod mouse_resolution;
 mod mouse_type;
+mod packet_builder;
+mod packet_queue;
+
+/// The default capacity of a [`Mouse`]'s internal packet queue, used unless a different
+/// capacity is specified via its const generic parameter.
+const DEFAULT_QUEUE_CAPACITY: usize = 15;
 
-const VALID_RESOLUTIONS: [u8; 4] = [1, 2, 4, 8];
 const VALID_SAMPLE_RATES: [u8; 7] = [10, 20, 40, 60, 80, 100, 200];
 
 type Result<T> = core::result::Result<T, MouseError>;
@@ -47,14 +60,88 @@ enum Command {
 /// let mut mouse = controller.mouse();
 /// ```
 #[derive(Debug)]
-pub struct Mouse<'c> {
+pub struct Mouse<'c, const QUEUE_CAPACITY: usize = DEFAULT_QUEUE_CAPACITY> {
     controller: &'c mut Controller,
+    mouse_type: MouseType,
+    builder: MousePacketBuilder,
+    queue: PacketQueue<QUEUE_CAPACITY>,
 }
 
-// TODO: Support Intellimouse extensions
-impl<'c> Mouse<'c> {
+impl<'c, const QUEUE_CAPACITY: usize> Mouse<'c, QUEUE_CAPACITY> {
     pub(crate) const fn new(controller: &'c mut Controller) -> Self {
-        Self { controller }
+        Self {
+            controller,
+            mouse_type: MouseType::Standard,
+            builder: MousePacketBuilder::new(MouseType::Standard),
+            queue: PacketQueue::new(),
+        }
+    }
+
+    /// Performs the full mouse bring-up sequence in one call: reset and self-test, set
+    /// defaults, negotiate the highest protocol up to `max_proto` that the hardware
+    /// acknowledges, then enable data reporting and stream mode.
+    ///
+    /// This replaces manually chaining [`Mouse::reset_and_self_test`],
+    /// [`Mouse::set_defaults`], [`Mouse::enable_intellimouse`],
+    /// [`Mouse::enable_intellimouse_5button`], [`Mouse::enable_data_reporting`], and
+    /// [`Mouse::set_stream_mode`]. Passing `max_proto` as [`MouseType::Standard`] skips
+    /// protocol escalation entirely, which is useful for hardware (e.g. some KVM
+    /// switches) that misbehaves when sent wheel-mode packets.
+    ///
+    /// Returns the negotiated [`MouseType`], which also determines whether
+    /// [`Mouse::read_data`] or [`Mouse::read_extended_data`] should be used to read
+    /// packets afterwards.
+    pub fn init(&mut self, max_proto: MouseType) -> Result<MouseType> {
+        self.reset_and_self_test()?;
+        self.set_defaults()?;
+
+        // `reset_and_self_test` drops the hardware back to standard 3-byte mode, so any
+        // protocol level negotiated before this call is no longer valid: reset our state
+        // to match before `knock`'s "only escalate" guard re-probes it.
+        self.mouse_type = MouseType::Standard;
+        self.builder.set_mouse_type(MouseType::Standard);
+
+        if max_proto >= MouseType::Wheel {
+            self.enable_intellimouse()?;
+        }
+        if max_proto >= MouseType::Quintuple {
+            self.enable_intellimouse_5button()?;
+        }
+
+        self.enable_data_reporting()?;
+        self.set_stream_mode()?;
+
+        Ok(self.mouse_type)
+    }
+
+    /// Feeds a single raw byte received from the mouse, e.g. one read from an interrupt
+    /// handler in streaming mode, into the internal packet assembler.
+    ///
+    /// Completed packets are pushed onto the internal queue rather than returned
+    /// directly; drain them later with [`Mouse::pop_packet`] or [`Mouse::drain_packets`].
+    /// If the queue is full, the oldest buffered packet is dropped to make room; see
+    /// [`Mouse::dropped_packet_count`].
+    pub fn push_byte(&mut self, byte: u8) {
+        if let Some(state) = self.builder.push(byte) {
+            self.queue.push(state);
+        }
+    }
+
+    /// Pops the oldest buffered packet, if any.
+    pub fn pop_packet(&mut self) -> Option<MouseState> {
+        self.queue.pop()
+    }
+
+    /// Drains all currently buffered packets, oldest first.
+    pub fn drain_packets(&mut self) -> impl Iterator<Item = MouseState> + '_ {
+        let queue = &mut self.queue;
+        core::iter::from_fn(move || queue.pop())
+    }
+
+    /// The number of packets dropped because the internal queue was full when they
+    /// arrived.
+    pub const fn dropped_packet_count(&self) -> u32 {
+        self.queue.dropped_count()
     }
 
     fn check_response(&mut self) -> Result<()> {
@@ -83,27 +170,17 @@ impl<'c> Mouse<'c> {
         self.write_command(Command::SetScaling2To1, None)
     }
 
-    pub fn set_resolution(&mut self, resolution: u8) -> Result<()> {
-        if !VALID_RESOLUTIONS.contains(&resolution) {
-            return Err(MouseError::InvalidResolution(resolution));
-        }
-        // Ok to unwrap since we already checked for existence in VALID_RESOLUTIONS.
-        // Also safe to cast to u8 since VALID_RESOLUTIONS has only 4 elements
-        let resolution_index = VALID_RESOLUTIONS
-            .iter()
-            .position(|&n| n == resolution)
-            .unwrap() as u8;
-        self.write_command(Command::SetResolution, Some(resolution_index))
+    pub fn set_resolution(&mut self, resolution: MouseResolution) -> Result<()> {
+        self.write_command(Command::SetResolution, Some(resolution as u8))
     }
 
-    pub fn request_status(&mut self) -> Result<(MouseStatus, u8, u8)> {
+    pub fn request_status(&mut self) -> Result<(MouseStatus, MouseResolution, u8)> {
         self.write_command(Command::StatusRequest, None)?;
         let status = MouseStatus::from_bits_truncate(self.controller.read_data()?);
         let resolution = self.controller.read_data()?;
         let sample_rate = self.controller.read_data()?;
-        if !VALID_RESOLUTIONS.contains(&resolution) {
-            return Err(MouseError::InvalidResolution(resolution));
-        }
+        let resolution =
+            MouseResolution::try_from(resolution).map_err(MouseError::InvalidResolution)?;
         if !VALID_SAMPLE_RATES.contains(&sample_rate) {
             return Err(MouseError::InvalidSampleRate(sample_rate));
         }
@@ -116,6 +193,41 @@ impl<'c> Mouse<'c> {
 
     pub fn read_data(&mut self) -> Result<(MouseMovement, i16, i16)> {
         self.write_command(Command::ReadData, None)?;
+        let movement = self.read_movement_packet()?;
+        // Once a wheel protocol is negotiated the hardware appends a 4th byte to every
+        // packet regardless of which method asked for it. Drain and discard it here so
+        // callers that don't care about wheel data can keep using this method without
+        // desyncing the byte stream for whatever reads the controller next.
+        if self.mouse_type != MouseType::Standard {
+            self.controller.read_data()?;
+        }
+        Ok(movement)
+    }
+
+    /// Reads a movement packet along with the wheel and extra-button data added by the
+    /// IntelliMouse extensions, once enabled with [`Mouse::enable_intellimouse`] or
+    /// [`Mouse::enable_intellimouse_5button`].
+    ///
+    /// On a mouse that hasn't negotiated either extension, the wheel delta is always `0`
+    /// and no extra buttons are ever reported.
+    pub fn read_extended_data(&mut self) -> Result<(MouseMovement, i16, i16, i8, ExtraButtons)> {
+        self.write_command(Command::ReadData, None)?;
+        let (movement_flags, x_movement, y_movement) = self.read_movement_packet()?;
+        let (wheel, extra_buttons) = match self.mouse_type {
+            MouseType::Standard => (0, ExtraButtons::empty()),
+            MouseType::Wheel => (self.controller.read_data()? as i8, ExtraButtons::empty()),
+            MouseType::Quintuple => {
+                let byte = self.controller.read_data()?;
+                (
+                    sign_extend_nibble(byte & 0x0f),
+                    ExtraButtons::from_bits_truncate(byte),
+                )
+            }
+        };
+        Ok((movement_flags, x_movement, y_movement, wheel, extra_buttons))
+    }
+
+    fn read_movement_packet(&mut self) -> Result<(MouseMovement, i16, i16)> {
         let movement_flags = MouseMovement::from_bits_truncate(self.controller.read_data()?);
         let mut x_movement = self.controller.read_data()? as u16;
         let mut y_movement = self.controller.read_data()? as u16;
@@ -130,6 +242,38 @@ impl<'c> Mouse<'c> {
         Ok((movement_flags, x_movement as i16, y_movement as i16))
     }
 
+    /// Performs the IntelliMouse "magic knock" (sample rates 200, 100, 80) that enables
+    /// scroll-wheel reporting on wheel-capable mice.
+    ///
+    /// Returns the negotiated [`MouseType`]; if the hardware doesn't support the
+    /// extension this comes back as [`MouseType::Standard`] and packets are unaffected.
+    pub fn enable_intellimouse(&mut self) -> Result<MouseType> {
+        self.knock([200, 100, 80])
+    }
+
+    /// Performs the IntelliMouse Explorer "magic knock" (sample rates 200, 200, 80) that
+    /// enables scroll-wheel and 4th/5th button reporting on 5-button-capable mice.
+    ///
+    /// Returns the negotiated [`MouseType`]; if the hardware doesn't support the
+    /// extension this comes back as whatever level was previously negotiated.
+    pub fn enable_intellimouse_5button(&mut self) -> Result<MouseType> {
+        self.knock([200, 200, 80])
+    }
+
+    fn knock(&mut self, sample_rates: [u8; 3]) -> Result<MouseType> {
+        for sample_rate in sample_rates {
+            self.set_sample_rate(sample_rate)?;
+        }
+        let mouse_type = self.get_device_id()?;
+        // Only escalate: a knock the hardware doesn't recognize shouldn't undo a
+        // previously-negotiated protocol level.
+        if mouse_type > self.mouse_type {
+            self.mouse_type = mouse_type;
+            self.builder.set_mouse_type(mouse_type);
+        }
+        Ok(mouse_type)
+    }
+
     pub fn reset_wrap_mode(&mut self) -> Result<()> {
         self.write_command(Command::ResetWrapMode, None)
     }
@@ -185,3 +329,13 @@ impl<'c> Mouse<'c> {
         result
     }
 }
+
+/// Sign-extends a 4-bit value (as found in the low nibble of an IntelliMouse Explorer
+/// wheel/button byte) to an `i8`.
+pub(crate) fn sign_extend_nibble(nibble: u8) -> i8 {
+    if nibble & 0b0000_1000 != 0 {
+        (nibble | 0b1111_0000) as i8
+    } else {
+        nibble as i8
+    }
+}
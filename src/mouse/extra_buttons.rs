@@ -0,0 +1,11 @@
+use bitflags::bitflags;
+
+bitflags! {
+    /// The 4th and 5th mouse buttons, reported in bits 4 and 5 of the 4th packet byte
+    /// once [`MouseType::Quintuple`](crate::mouse::MouseType::Quintuple) mode is active.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ExtraButtons: u8 {
+        const FOURTH_BUTTON = 0b0001_0000;
+        const FIFTH_BUTTON = 0b0010_0000;
+    }
+}
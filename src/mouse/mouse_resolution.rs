@@ -0,0 +1,26 @@
+/// A mouse's movement resolution, in counts per millimeter.
+///
+/// This is the decoded form of the raw `0..=3` value sent to the `Set Resolution`
+/// (`0xe8`) command and read back from `Status Request` (`0xe9`) on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MouseResolution {
+    Count1PerMm = 0,
+    Count2PerMm = 1,
+    Count4PerMm = 2,
+    Count8PerMm = 3,
+}
+
+impl TryFrom<u8> for MouseResolution {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(MouseResolution::Count1PerMm),
+            1 => Ok(MouseResolution::Count2PerMm),
+            2 => Ok(MouseResolution::Count4PerMm),
+            3 => Ok(MouseResolution::Count8PerMm),
+            other => Err(other),
+        }
+    }
+}
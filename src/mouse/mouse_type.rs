@@ -0,0 +1,27 @@
+/// The protocol level a PS/2 mouse is operating at, as reported by the `Get Device ID`
+/// (`0xf2`) command.
+///
+/// This reflects which IntelliMouse "magic knock" sample-rate sequence (if any) the
+/// device has acknowledged, and therefore how many bytes make up each movement packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MouseType {
+    /// A standard 3-button mouse, sending 3-byte packets.
+    Standard,
+    /// An IntelliMouse-compatible mouse with a scroll wheel, sending 4-byte packets with
+    /// a Z-axis (wheel) delta in the 4th byte.
+    Wheel,
+    /// An IntelliMouse Explorer-compatible mouse with a scroll wheel and two extra
+    /// buttons, sending 4-byte packets with the wheel delta and extra buttons packed
+    /// into the 4th byte.
+    Quintuple,
+}
+
+impl From<u8> for MouseType {
+    fn from(device_id: u8) -> Self {
+        match device_id {
+            0x03 => MouseType::Wheel,
+            0x04 => MouseType::Quintuple,
+            _ => MouseType::Standard,
+        }
+    }
+}
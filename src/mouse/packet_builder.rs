@@ -0,0 +1,175 @@
+use super::{sign_extend_nibble, ExtraButtons, MouseType};
+use crate::flags::MouseMovement;
+
+/// A fully-assembled mouse movement packet, as produced by [`MousePacketBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseState {
+    pub buttons: MouseMovement,
+    pub dx: i16,
+    pub dy: i16,
+    /// The scroll-wheel delta, if the packet was assembled in a wheel-capable
+    /// [`MouseType`].
+    pub wheel: Option<i8>,
+    /// The 4th/5th button state, if the packet was assembled in
+    /// [`MouseType::Quintuple`].
+    pub extra_buttons: Option<ExtraButtons>,
+}
+
+/// Assembles raw PS/2 mouse bytes, arriving one at a time as from an interrupt handler in
+/// streaming mode, into complete [`MouseState`] packets.
+///
+/// Byte 0 of every packet has the "always 1" bit (bit 3) set in its status byte. A byte
+/// that arrives at index 0 without that bit set is discarded and the builder stays at
+/// index 0, so a single dropped or spurious byte doesn't permanently desynchronize it from
+/// the packet boundary.
+///
+/// # Examples
+/// ```
+/// use ps2::mouse::{MousePacketBuilder, MouseType};
+///
+/// let mut builder = MousePacketBuilder::new(MouseType::Standard);
+///
+/// // A byte without the "always 1" bit (bit 3) set can't start a packet; it's
+/// // discarded and the builder stays at index 0 to resynchronize.
+/// assert_eq!(builder.push(0x00), None);
+///
+/// // The next well-formed packet still completes in exactly 3 bytes, not 4 -
+/// // the bad byte above wasn't counted as part of it.
+/// assert_eq!(builder.push(0x08), None);
+/// assert_eq!(builder.push(0x01), None);
+/// assert!(builder.push(0x00).is_some());
+/// ```
+#[derive(Debug)]
+pub struct MousePacketBuilder {
+    mouse_type: MouseType,
+    pending_mouse_type: MouseType,
+    index: usize,
+    bytes: [u8; 4],
+}
+
+impl MousePacketBuilder {
+    pub const fn new(mouse_type: MouseType) -> Self {
+        Self {
+            mouse_type,
+            pending_mouse_type: mouse_type,
+            index: 0,
+            bytes: [0; 4],
+        }
+    }
+
+    /// Updates the protocol level used to interpret incoming packets, e.g. after
+    /// negotiating wheel support mid-stream. Takes effect starting with the next packet.
+    pub fn set_mouse_type(&mut self, mouse_type: MouseType) {
+        self.pending_mouse_type = mouse_type;
+    }
+
+    const fn packet_len(&self) -> usize {
+        match self.mouse_type {
+            MouseType::Standard => 3,
+            MouseType::Wheel | MouseType::Quintuple => 4,
+        }
+    }
+
+    /// Feeds a single byte into the assembler, returning a [`MouseState`] once a full
+    /// packet has been collected.
+    pub fn push(&mut self, byte: u8) -> Option<MouseState> {
+        if self.index == 0 {
+            self.mouse_type = self.pending_mouse_type;
+        }
+        if self.index == 0 && byte & 0b0000_1000 == 0 {
+            return None;
+        }
+
+        self.bytes[self.index] = byte;
+        self.index += 1;
+        if self.index < self.packet_len() {
+            return None;
+        }
+
+        self.index = 0;
+        Some(self.assemble())
+    }
+
+    fn assemble(&self) -> MouseState {
+        let buttons = MouseMovement::from_bits_truncate(self.bytes[0]);
+        let mut dx = self.bytes[1] as u16;
+        let mut dy = self.bytes[2] as u16;
+
+        if buttons.contains(MouseMovement::X_SIGN_BIT) {
+            dx |= 0xff00;
+        }
+        if buttons.contains(MouseMovement::Y_SIGN_BIT) {
+            dy |= 0xff00;
+        }
+
+        let (wheel, extra_buttons) = match self.mouse_type {
+            MouseType::Standard => (None, None),
+            MouseType::Wheel => (Some(self.bytes[3] as i8), None),
+            MouseType::Quintuple => (
+                Some(sign_extend_nibble(self.bytes[3] & 0x0f)),
+                Some(ExtraButtons::from_bits_truncate(self.bytes[3])),
+            ),
+        };
+
+        MouseState {
+            buttons,
+            dx: dx as i16,
+            dy: dy as i16,
+            wheel,
+            extra_buttons,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discards_byte_without_sync_bit_and_resyncs() {
+        let mut builder = MousePacketBuilder::new(MouseType::Standard);
+        assert_eq!(builder.push(0x00), None);
+        assert_eq!(builder.push(0x08), None);
+        assert_eq!(builder.push(1), None);
+        assert!(builder.push(2).is_some());
+    }
+
+    #[test]
+    fn assembles_standard_3_byte_packet_with_signed_deltas() {
+        let mut builder = MousePacketBuilder::new(MouseType::Standard);
+        assert_eq!(builder.push(0b0011_1000), None); // sync bit + X/Y sign bits
+        assert_eq!(builder.push(0xfb), None); // -5
+        let state = builder.push(0xf6).unwrap(); // -10
+        assert_eq!(state.dx, -5);
+        assert_eq!(state.dy, -10);
+        assert_eq!(state.wheel, None);
+        assert_eq!(state.extra_buttons, None);
+    }
+
+    #[test]
+    fn wheel_mode_decodes_8_bit_wheel_delta() {
+        let mut builder = MousePacketBuilder::new(MouseType::Wheel);
+        assert_eq!(builder.push(0x08), None);
+        assert_eq!(builder.push(10), None);
+        assert_eq!(builder.push(20), None);
+        let state = builder.push(0xfe).unwrap(); // -2
+        assert_eq!(state.dx, 10);
+        assert_eq!(state.dy, 20);
+        assert_eq!(state.wheel, Some(-2));
+        assert_eq!(state.extra_buttons, None);
+    }
+
+    #[test]
+    fn quintuple_mode_decodes_nibble_wheel_and_extra_buttons() {
+        let mut builder = MousePacketBuilder::new(MouseType::Quintuple);
+        assert_eq!(builder.push(0x08), None);
+        assert_eq!(builder.push(0), None);
+        assert_eq!(builder.push(0), None);
+        let state = builder.push(0b0011_1110).unwrap();
+        assert_eq!(state.wheel, Some(-2));
+        assert_eq!(
+            state.extra_buttons,
+            Some(ExtraButtons::FOURTH_BUTTON | ExtraButtons::FIFTH_BUTTON)
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use super::MouseState;
+
+/// A fixed-capacity ring buffer of assembled [`MouseState`] packets.
+///
+/// This crate is `no_std`, so the buffer is backed by an inline array rather than
+/// `alloc`. Pushing onto a full queue drops the oldest packet and increments
+/// [`PacketQueue::dropped_count`], mirroring how OS PS/2 mouse drivers decouple a
+/// high-frequency interrupt producer from a slower consumer without blocking the IRQ.
+#[derive(Debug)]
+pub struct PacketQueue<const CAPACITY: usize> {
+    packets: [Option<MouseState>; CAPACITY],
+    head: usize,
+    len: usize,
+    dropped: u32,
+}
+
+impl<const CAPACITY: usize> PacketQueue<CAPACITY> {
+    pub const fn new() -> Self {
+        assert!(CAPACITY > 0, "PacketQueue capacity must be greater than 0");
+        Self {
+            packets: [None; CAPACITY],
+            head: 0,
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    pub fn push(&mut self, packet: MouseState) {
+        let tail = (self.head + self.len) % CAPACITY;
+        if self.len == CAPACITY {
+            self.head = (self.head + 1) % CAPACITY;
+            self.dropped = self.dropped.saturating_add(1);
+        } else {
+            self.len += 1;
+        }
+        self.packets[tail] = Some(packet);
+    }
+
+    pub fn pop(&mut self) -> Option<MouseState> {
+        let packet = self.packets[self.head].take()?;
+        self.head = (self.head + 1) % CAPACITY;
+        self.len -= 1;
+        Some(packet)
+    }
+
+    /// The number of packets silently dropped because the queue was full when they
+    /// arrived.
+    pub const fn dropped_count(&self) -> u32 {
+        self.dropped
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<const CAPACITY: usize> Default for PacketQueue<CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flags::MouseMovement;
+
+    fn state(dx: i16) -> MouseState {
+        MouseState {
+            buttons: MouseMovement::empty(),
+            dx,
+            dy: 0,
+            wheel: None,
+            extra_buttons: None,
+        }
+    }
+
+    #[test]
+    fn pops_in_fifo_order() {
+        let mut queue: PacketQueue<3> = PacketQueue::new();
+        queue.push(state(1));
+        queue.push(state(2));
+        queue.push(state(3));
+        assert_eq!(queue.pop().unwrap().dx, 1);
+        assert_eq!(queue.pop().unwrap().dx, 2);
+        assert_eq!(queue.pop().unwrap().dx, 3);
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn drops_oldest_on_overflow_and_counts_drops() {
+        let mut queue: PacketQueue<2> = PacketQueue::new();
+        queue.push(state(1));
+        queue.push(state(2));
+        assert_eq!(queue.dropped_count(), 0);
+
+        queue.push(state(3));
+        assert_eq!(queue.dropped_count(), 1);
+        assert_eq!(queue.pop().unwrap().dx, 2);
+        assert_eq!(queue.pop().unwrap().dx, 3);
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn len_and_is_empty_track_contents() {
+        let mut queue: PacketQueue<4> = PacketQueue::new();
+        assert!(queue.is_empty());
+
+        queue.push(state(1));
+        assert_eq!(queue.len(), 1);
+        assert!(!queue.is_empty());
+
+        queue.pop();
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be greater than 0")]
+    fn rejects_zero_capacity() {
+        let _queue: PacketQueue<0> = PacketQueue::new();
+    }
+}